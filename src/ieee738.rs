@@ -1,6 +1,8 @@
 // This code was written while referencing https://github.com/tommz9/pylinerating
 // Credit to Tomas Barton for initial calculation methods. (Thanks so much!)
 
+use rand_distr::{Distribution, Normal};
+
 pub struct Conductor {
     pub stranded: bool,
     pub high_rs: bool,
@@ -67,6 +69,16 @@ pub fn reynolds_number(
         / dynamic_viscosity(ambient_temperature, conductor_temperature)
 }
 
+/// Component terms behind `forced_convection`'s result, exposed so callers
+/// (e.g. `thermal_rating_detailed`) can audit the wind-direction factor and
+/// Reynolds number rather than just the winning Qc term.
+pub struct ForcedConvection {
+    pub qc1: f64,
+    pub qc2: f64,
+    pub k_angle: f64,
+    pub reynolds: f64,
+}
+
 pub fn forced_convection(
     ambient_temperature: f64,
     wind_speed: f64,
@@ -74,7 +86,7 @@ pub fn forced_convection(
     conductor: &Conductor,
     conductor_temperature: f64,
     elevation: f64,
-) -> (f64, Option<(f64, f64, f64, f64)>) {
+) -> (f64, ForcedConvection) {
     let kangle = 1.194
         - angle_of_attack.cos()
         + 0.194 * (2.0 * angle_of_attack).cos()
@@ -96,7 +108,7 @@ pub fn forced_convection(
         * kf
         * (conductor_temperature - ambient_temperature);
 
-    (f64::max(qc1, qc2), Some((qc1, qc2, kangle, nre)))
+    (f64::max(qc1, qc2), ForcedConvection { qc1, qc2, k_angle: kangle, reynolds: nre })
 }
 
 pub fn natural_convection(
@@ -119,7 +131,7 @@ pub fn convective_heat_loss(
     conductor_temperature: f64,
     elevation: f64,
 ) -> f64 {
-    let forced: (f64, Option<(f64, f64, f64, f64)>) = forced_convection(
+    let forced: (f64, ForcedConvection) = forced_convection(
         ambient_temperature,
         wind_speed,
         angle_of_attack,
@@ -153,6 +165,103 @@ pub fn solar_heat_gain(solar_irradiation: f64, conductor: &Conductor) -> f64 {
     conductor.absorptivity * solar_irradiation * conductor.diameter
 }
 
+/// Calculates day of year.
+/// # Arguments
+/// * `month` - Month January (1) to December (12)
+/// * `day_of_month` - Day of Month, 1 to 31
+fn day_of_year(month: i32, day_of_month: i32) -> i32 {
+    let days_in_month = [0, 31, 28, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31];
+
+    let mut result = day_of_month;
+    for i in 1..month {
+        result += days_in_month[i as usize];
+    }
+    result
+}
+
+/// Returns solar_heat_gain Qs (W/m), deriving the solar input from
+/// date/time and line geometry instead of requiring a pre-measured
+/// `solar_irradiation`.
+/// # Arguments
+/// * `month` - 1 (January) to 12 (December)
+/// * `day_of_month` - Day of Month (1-31)
+/// * `hour_of_day` - Hour of Day, 0 to 23 (e.g. 11:00 AM => 11)
+/// * `latitude_deg` - Lat: Latitude (Decimal Degrees)
+/// * `line_azimuth_deg` - Zl: If line runs E-W => 90 Degrees
+/// * `elevation` - He: Height of conductor above sea level (m)
+/// * `atmosphere_clear` - Clear? (True) Industrial? (False)
+/// * `conductor` - Conductor properties
+pub fn solar_heat_gain_position(
+    month: i32,
+    day_of_month: i32,
+    hour_of_day: f64,
+    latitude_deg: f64,
+    line_azimuth_deg: f64,
+    elevation: f64,
+    atmosphere_clear: bool,
+    conductor: &Conductor,
+) -> f64 {
+    let pi = std::f64::consts::PI;
+
+    let day_of_year = day_of_year(month, day_of_month);
+
+    let latitude_rad = latitude_deg * (pi / 180.0);
+
+    // Hour angle relative to noon, 15*(Time-12)
+    let w_deg = (hour_of_day - 12.0) * 15.0;
+    let w_rad = w_deg * (pi / 180.0);
+
+    // Table 3 - Atmosphere condition coefficients
+    let (a, b, c, d, e, f, g) = match atmosphere_clear {
+        true => (-3.9241, 5.9276, -1.7856e-1, 3.223e-3, -3.3549e-5, 1.8053e-7, -3.7868e-10),
+        false => (4.9408, 1.3208, 6.1444e-2, -2.9411e-3, 5.07752e-5, -4.03627e-7, 1.22967e-9),
+    };
+
+    // Solar declination δ = 23.46*sin(360*(284+N)/365)
+    let delta_deg = 23.46 * ((360.0 * (284.0 + day_of_year as f64) / 365.0) * (pi / 180.0)).sin();
+    let delta_rad = delta_deg * (pi / 180.0);
+
+    // Solar altitude Hc
+    let hc_rad = (latitude_rad.cos() * delta_rad.cos() * w_rad.cos() + latitude_rad.sin() * delta_rad.sin()).asin();
+    let hc_deg = hc_rad * (180.0 / pi);
+
+    // Total solar and sky radiated heat intensity
+    let qs = a + b * hc_deg + c * hc_deg.powi(2) + d * hc_deg.powi(3) + e * hc_deg.powi(4) + f * hc_deg.powi(5) + g * hc_deg.powi(6);
+
+    // The coefficients above give W/ft^2; convert to W/m^2 (10.7639 ft^2 per m^2).
+    let qs = qs * 10.7639;
+
+    // Elevation multiplier (1 + 1.148e-4*He - 1.108e-8*He^2)
+    let ksolar = 1.0 + 1.148e-4 * elevation - 1.108e-8 * elevation.powi(2);
+
+    // Qs sometimes can compute as less than 0, if the sun is down. The lowest heating you can have is 0.
+    let qse = f64::max(qs, 0.0) * ksolar;
+
+    let x = w_rad.sin() / (latitude_rad.sin() * w_rad.cos() - latitude_rad.cos() * delta_rad.tan());
+
+    let cc_deg =
+        if -180.0 <= w_deg && w_deg < 0.0 {
+            if x >= 0.0 { 0.0 }
+            else { 180.0 }
+        } else {
+            if x < 0.0 { 180.0 }
+            else { 360.0 }
+        };
+
+    let cc_rad = cc_deg * (pi / 180.0);
+
+    // Azimuth of line
+    let zl_rad = line_azimuth_deg * (pi / 180.0);
+
+    // Azimuth of sun
+    let zc_rad = cc_rad + x.atan();
+
+    // Effective angle of incidence of the sun's rays
+    let theta = (hc_rad.cos() * (zc_rad - zl_rad).cos()).acos();
+
+    conductor.absorptivity * qse * theta.sin() * conductor.diameter
+}
+
 pub fn thermal_rating(
     ambient_temperature: f64,
     wind_speed: f64,
@@ -163,11 +272,51 @@ pub fn thermal_rating(
     horizontal_angle: f64,
     elevation: f64,
 ) -> f64 {
+    thermal_rating_detailed(
+        ambient_temperature,
+        wind_speed,
+        angle_of_attack,
+        solar_irradiation,
+        conductor,
+        conductor_temperature,
+        horizontal_angle,
+        elevation,
+    ).current
+}
+
+/// Full heat-balance breakdown behind a `thermal_rating` result, so callers
+/// can audit which convection regime dominated, inspect the Reynolds number
+/// and wind-direction factor, and verify each watt-per-meter term.
+pub struct HeatBalance {
+    pub convective_loss: f64,
+    pub radiated_loss: f64,
+    pub solar_gain: f64,
+    pub forced_qc1: f64,
+    pub forced_qc2: f64,
+    pub natural_qc: f64,
+    pub reynolds: f64,
+    pub k_angle: f64,
+    pub resistance: f64,
+    pub current: f64,
+}
+
+/// Same calculation as `thermal_rating`, but returns the full `HeatBalance`
+/// breakdown instead of a bare ampacity scalar.
+pub fn thermal_rating_detailed(
+    ambient_temperature: f64,
+    wind_speed: f64,
+    angle_of_attack: f64,
+    solar_irradiation: f64,
+    conductor: &Conductor,
+    conductor_temperature: f64,
+    horizontal_angle: f64,
+    elevation: f64,
+) -> HeatBalance {
     // the angle must be in the range 0-90
     let angle_of_attack = 90.0 - ((angle_of_attack % 180.0) - 90.0).abs();
     let angle_of_attack = (angle_of_attack / 180.0) * std::f64::consts::PI;
 
-    let qc = convective_heat_loss(
+    let (forced_qc, forced) = forced_convection(
         ambient_temperature,
         wind_speed,
         angle_of_attack,
@@ -176,12 +325,235 @@ pub fn thermal_rating(
         elevation,
     );
 
+    let natural_qc = natural_convection(ambient_temperature, &conductor, conductor_temperature, elevation);
+
+    let qc = f64::max(forced_qc, natural_qc);
+
     let qr = radiated_heat_loss(ambient_temperature, &conductor, conductor_temperature);
 
     let qs = solar_heat_gain(solar_irradiation, &conductor);
 
-    let current = ((qc + qr - qs) / adjust_r(conductor_temperature, conductor.r_at_25, conductor.r_at_75)).sqrt();
+    let r = adjust_r(conductor_temperature, conductor.r_at_25, conductor.r_at_75);
+
+    // Net heat balance can go negative (e.g. low current, strong solar gain,
+    // cool conductor) for some sampled weather in `probabilistic_rating`; that
+    // just means no current is needed to hold `conductor_temperature`, not an
+    // imaginary ampacity, so clamp before taking the square root.
+    let current = f64::max(0.0, (qc + qr - qs) / r).sqrt();
+
+    HeatBalance {
+        convective_loss: qc,
+        radiated_loss: qr,
+        solar_gain: qs,
+        forced_qc1: forced.qc1,
+        forced_qc2: forced.qc2,
+        natural_qc,
+        reynolds: forced.reynolds,
+        k_angle: forced.k_angle,
+        resistance: r,
+        current,
+    }
+}
+
+/// Default number of weather samples drawn by `probabilistic_rating` when
+/// the caller has no stronger preference.
+pub const DEFAULT_SAMPLE_COUNT: usize = 10_000;
+
+/// Returns a probabilistic (Monte Carlo) dynamic line rating (Amps).
+///
+/// Ambient temperature and wind speed (and optionally wind angle) are
+/// treated as normally distributed random variables rather than fixed
+/// inputs. `sample_count` weather realizations are drawn, `thermal_rating`
+/// is evaluated for each, and the resulting ampacities are sorted into an
+/// empirical distribution. The value returned is the `risk`-quantile of
+/// that distribution, i.e. the current for which the probability of the
+/// conductor exceeding its maximum temperature is no greater than `risk`
+/// (e.g. `risk = 0.01` for a 1% exceedance probability).
+/// # Arguments
+/// * `ambient_temperature_mean` - Mean T_a: Degrees (C)
+/// * `ambient_temperature_sigma` - Standard deviation of T_a: Degrees (C)
+/// * `wind_speed_mean` - Mean V_w: Wind Speed (m/s)
+/// * `wind_speed_sigma` - Standard deviation of V_w: Wind Speed (m/s)
+/// * `wind_angle_mean` - Mean wind angle of attack (Radians)
+/// * `wind_angle_sigma` - Standard deviation of wind angle of attack (Radians)
+/// * `solar_irradiation` - Solar Irradiation (W/m^2)
+/// * `conductor` - Conductor properties
+/// * `conductor_temperature` - Maximum Operating Temperature (C)
+/// * `horizontal_angle` - Horizontal Angle (unused, carried through from `thermal_rating`)
+/// * `elevation` - Height of conductor above sea level (m)
+/// * `risk` - Acceptable probability (0.0 to 1.0) of exceeding `conductor_temperature`
+/// * `sample_count` - Number of weather samples to draw (e.g. `DEFAULT_SAMPLE_COUNT`)
+pub fn probabilistic_rating(
+    ambient_temperature_mean: f64,
+    ambient_temperature_sigma: f64,
+    wind_speed_mean: f64,
+    wind_speed_sigma: f64,
+    wind_angle_mean: f64,
+    wind_angle_sigma: f64,
+    solar_irradiation: f64,
+    conductor: &Conductor,
+    conductor_temperature: f64,
+    horizontal_angle: f64,
+    elevation: f64,
+    risk: f64,
+    sample_count: usize,
+) -> f64 {
+    assert!(sample_count > 0, "sample_count must be at least 1");
+
+    let mut rng = rand::thread_rng();
+
+    let ambient_temperature_dist = Normal::new(ambient_temperature_mean, ambient_temperature_sigma).unwrap();
+    let wind_speed_dist = Normal::new(wind_speed_mean, wind_speed_sigma).unwrap();
+    let wind_angle_dist = Normal::new(wind_angle_mean, wind_angle_sigma).unwrap();
+
+    let mut ratings: Vec<f64> = (0..sample_count)
+        .map(|_| {
+            let ambient_temperature = ambient_temperature_dist.sample(&mut rng);
+            // Wind speed cannot be negative.
+            let wind_speed = wind_speed_dist.sample(&mut rng).max(0.0);
+            // `thermal_rating` expects `angle_of_attack` in degrees.
+            let angle_of_attack = wind_angle_dist.sample(&mut rng).to_degrees();
+
+            thermal_rating(
+                ambient_temperature,
+                wind_speed,
+                angle_of_attack,
+                solar_irradiation,
+                conductor,
+                conductor_temperature,
+                horizontal_angle,
+                elevation,
+            )
+        })
+        .collect();
+
+    ratings.sort_by(|a, b| a.total_cmp(b));
+
+    // The risk-quantile: only `risk` fraction of sampled weather is worse
+    // (i.e. would give a lower safe ampacity) than the returned rating.
+    let index = (risk * (ratings.len() - 1) as f64).round() as usize;
+    ratings[index.min(ratings.len() - 1)]
+}
+
+/// Horizontal tension and sag of a conductor at a target operating
+/// temperature, as solved by `conductor_sag_tension`.
+pub struct SagTension {
+    pub horizontal_tension: f64,
+    pub sag: f64,
+}
+
+/// Solves the ruling-span change-of-state equation
+/// `H2^2 * (H2 - H1 + w^2*S^2*E*A/(24*H1^2) + E*A*α*(t2 - t1)) = w^2*S^2*E*A/24`
+/// for the new horizontal tension H2 at conductor temperature `t2` (Newton
+/// iteration seeded at `h1`), then reports the resulting parabolic sag
+/// `D = w*S^2/(8*H2)`.
+/// # Arguments
+/// * `span_length` - S: Ruling span length (m)
+/// * `weight_per_length` - w: Conductor weight per unit length (N/m)
+/// * `elastic_modulus` - E: Final modulus of elasticity (Pa)
+/// * `cross_section` - A: Conductor cross-sectional area (m^2)
+/// * `thermal_expansion` - α: Coefficient of linear thermal expansion (1/°C)
+/// * `h1` - H1: Horizontal tension at reference temperature `t1` (N)
+/// * `t1` - Reference conductor temperature (C)
+/// * `t2` - Target conductor temperature (C), e.g. from `calculated_temperature`
+pub fn conductor_sag_tension(
+    span_length: f64,
+    weight_per_length: f64,
+    elastic_modulus: f64,
+    cross_section: f64,
+    thermal_expansion: f64,
+    h1: f64,
+    t1: f64,
+    t2: f64,
+) -> SagTension {
+    let ea = elastic_modulus * cross_section;
+    let w2s2 = weight_per_length.powi(2) * span_length.powi(2);
+    let elastic_term = w2s2 * ea / (24.0 * h1.powi(2));
+    let thermal_term = ea * thermal_expansion * (t2 - t1);
+    let rhs = w2s2 * ea / 24.0;
+
+    // Newton iteration on g(H2) = H2^3 + H2^2*(elastic_term + thermal_term - H1) - rhs = 0
+    let offset = elastic_term + thermal_term - h1;
+    let mut h2 = h1;
+    for _ in 0..50 {
+        let g = h2.powi(3) + h2.powi(2) * offset - rhs;
+        let g_prime = 3.0 * h2.powi(2) + 2.0 * h2 * offset;
+        let step = g / g_prime;
+        h2 -= step;
+        if step.abs() < 1e-9 {
+            break;
+        }
+    }
+
+    SagTension {
+        horizontal_tension: h2,
+        sag: weight_per_length * span_length.powi(2) / (8.0 * h2),
+    }
+}
+
+// Time-at-temperature annealing constants for 1350-H19 hard-drawn aluminum,
+// from the established loss-of-tensile-strength relation used to estimate
+// emergency rating limits.
+pub const ANNEAL_A_1350_H19: f64 = -1.4;
+pub const ANNEAL_B_1350_H19: f64 = 0.0097;
+pub const ANNEAL_M_1350_H19: f64 = 0.167;
+
+/// Returns the percent of original tensile strength remaining (0-100) after
+/// operating at `conductor_temperature` for `exposure_hours`, via
+/// `W = 100 - d * exp(A + B*T) * t^m`.
+/// # Arguments
+/// * `conductor_temperature` - T: Conductor Surface Temperature (C)
+/// * `exposure_hours` - t: Cumulative exposure time at `conductor_temperature` (hours)
+/// * `wire_diameter_mm` - d: Individual strand wire diameter (mm)
+/// * `a` - Material constant A (e.g. `ANNEAL_A_1350_H19`)
+/// * `b` - Material constant B (e.g. `ANNEAL_B_1350_H19`)
+/// * `m` - Material constant m (e.g. `ANNEAL_M_1350_H19`)
+pub fn remaining_strength(
+    conductor_temperature: f64,
+    exposure_hours: f64,
+    wire_diameter_mm: f64,
+    a: f64,
+    b: f64,
+    m: f64,
+) -> f64 {
+    if exposure_hours <= 0.0 {
+        return 100.0;
+    }
+
+    let loss = wire_diameter_mm * (a + b * conductor_temperature).exp() * exposure_hours.powf(m);
+    f64::max(0.0, 100.0 - loss)
+}
+
+/// Accumulates loss of tensile strength over a simulated loading history,
+/// mirroring the per-step stepping loop in `conductor_temperature_rise`.
+/// Each entry in `conductor_temperatures` is held for `time_step_hours`
+/// before advancing to the next; the returned value is the percent of
+/// original tensile strength remaining (0-100) after the worst exposure
+/// encountered.
+/// # Arguments
+/// * `conductor_temperatures` - Conductor temperature (C) for each time step, e.g. from `calculated_temperature`
+/// * `time_step_hours` - Duration of each step (hours)
+/// * `wire_diameter_mm` - d: Individual strand wire diameter (mm)
+/// * `a` - Material constant A (e.g. `ANNEAL_A_1350_H19`)
+/// * `b` - Material constant B (e.g. `ANNEAL_B_1350_H19`)
+/// * `m` - Material constant m (e.g. `ANNEAL_M_1350_H19`)
+pub fn cumulative_remaining_strength(
+    conductor_temperatures: &[f64],
+    time_step_hours: f64,
+    wire_diameter_mm: f64,
+    a: f64,
+    b: f64,
+    m: f64,
+) -> f64 {
+    let mut exposure_hours = 0.0;
+    let mut worst_remaining = 100.0;
+
+    for &conductor_temperature in conductor_temperatures {
+        exposure_hours += time_step_hours;
+        let remaining = remaining_strength(conductor_temperature, exposure_hours, wire_diameter_mm, a, b, m);
+        worst_remaining = f64::min(worst_remaining, remaining);
+    }
 
-    current
+    worst_remaining
 }
 
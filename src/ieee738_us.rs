@@ -1,14 +1,40 @@
 
 
-/// Returns convective_heat_loss Qc (Watts / ft)
+/// Imperial (US-customary) or SI (metric) input/output unit convention for
+/// the coefficient sets used throughout this module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Units {
+    Imperial,
+    SI,
+}
+
+/// Which term won the max() in `convective_heat_loss`: natural convection,
+/// or one of the two forced-convection equations (3a for low-speed wind,
+/// 3b for high-speed wind).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConvectionRegime {
+    Natural,
+    ForcedLowWind,
+    ForcedHighWind,
+}
+
+/// The winning convective heat loss together with which regime produced it.
+pub struct ConvectiveHeatLoss {
+    pub loss: f64,
+    pub regime: ConvectionRegime,
+}
+
+/// Returns convective_heat_loss Qc (Watts / ft, or Watts / m for `Units::SI`)
 /// # Arguments
+/// * `units` - Imperial (ft, ft/s) or SI (m, m/s) input/output convention
 /// * `ambient_temperature` - T_a: Degrees (C)
-/// * `wind_speed` - V_w: Wind Speed (ft/s)
+/// * `wind_speed` - V_w: Wind Speed (ft/s, or m/s for `Units::SI`)
 /// * `wind_angle_deg` - Wind Angle (Degrees) 0 to 90
-/// * `elevation` - H_e: Height of conductor above sea level (ft)
+/// * `elevation` - H_e: Height of conductor above sea level (ft, or m for `Units::SI`)
 /// * `conductor_temperature` - T_s: Conductor Surface Temperature (C)
-/// * `diameter` - D_0: Outer diameter of the conductor (ft)
+/// * `diameter` - D_0: Outer diameter of the conductor (ft, or m for `Units::SI`)
 pub fn convective_heat_loss(
+    units: Units,
     ambient_temperature: f64,
     wind_speed: f64,
     wind_angle_deg: f64,
@@ -16,43 +42,81 @@ pub fn convective_heat_loss(
     conductor_temperature: f64,
     diameter: f64,
 ) -> f64 {
+    convective_heat_loss_detailed(units, ambient_temperature, wind_speed, wind_angle_deg, elevation, conductor_temperature, diameter).loss
+}
+
+/// Same calculation as `convective_heat_loss`, but also reports which of
+/// natural or forced convection (and which forced-convection equation)
+/// produced the winning value.
+/// # Arguments
+/// * `units` - Imperial (ft, ft/s) or SI (m, m/s) input/output convention
+/// * `ambient_temperature` - T_a: Degrees (C)
+/// * `wind_speed` - V_w: Wind Speed (ft/s, or m/s for `Units::SI`)
+/// * `wind_angle_deg` - Wind Angle (Degrees) 0 to 90
+/// * `elevation` - H_e: Height of conductor above sea level (ft, or m for `Units::SI`)
+/// * `conductor_temperature` - T_s: Conductor Surface Temperature (C)
+/// * `diameter` - D_0: Outer diameter of the conductor (ft, or m for `Units::SI`)
+pub fn convective_heat_loss_detailed(
+    units: Units,
+    ambient_temperature: f64,
+    wind_speed: f64,
+    wind_angle_deg: f64,
+    elevation: f64,
+    conductor_temperature: f64,
+    diameter: f64,
+) -> ConvectiveHeatLoss {
     let pi = std::f64::consts::PI;
 
     // Limit to within 0-90.
     let wind_angle_deg_limited = 90.0 - (wind_angle_deg % 180.0 - 90.0).abs();
     let wind_angle_rad = wind_angle_deg_limited * (pi / 180.0);
 
-    // Equation 6, Tfilm W/ft (degrees C)
+    // Equation 6, Tfilm (degrees C)
     let tfilm = (conductor_temperature + ambient_temperature) / 2.0;
 
-    // Absolute Viscosity of Air (m_f), (lb/ft*h)
-    // dynamic_viscosity
-    // Equation 13b
-    let uf = 0.00353 * (tfilm + 273.15).powf(1.5) / (tfilm + 383.4);
-
-    // air_density (lb/ft^3)
-    // Equation 14b
-    let pf = (0.080695 - 2.901e-6 * elevation + 3.7e-11 * elevation.powi(2)) / (1.0 + 0.00367 * tfilm);
-
     // Equation 4a Section 4.4.3.1, page 11.
     let kangle = 1.194
         - wind_angle_rad.cos()
         + 0.194 * (2.0 * wind_angle_rad).cos()
         + 0.368 * (2.0 * wind_angle_rad).sin();
 
-    // Equation 2c
-    let nre = diameter
-        * pf
-        * (wind_speed * 60.0 * 60.0) // Because dynamic_viscosity is in lb/ft-hr, we must convert wind speed to ft/hr.
-        / uf;
+    let (pf, kf, nre, qcn_coefficient) = match units {
+        Units::Imperial => {
+            // Absolute Viscosity of Air (m_f), (lb/ft*h)
+            // dynamic_viscosity, Equation 13b
+            let uf = 0.00353 * (tfilm + 273.15).powf(1.5) / (tfilm + 383.4);
+
+            // air_density (lb/ft^3), Equation 14b
+            let pf = (0.080695 - 2.901e-6 * elevation + 3.7e-11 * elevation.powi(2)) / (1.0 + 0.00367 * tfilm);
+
+            // thermal_conductivity_of_air, Equation 15b
+            let kf = 7.388e-3 + 2.279e-5 * tfilm - 1.343e-9 * tfilm.powi(2);
 
-    // thermal_conductivity_of_air
-    // Equation 15b
-    let kf = 7.388e-3 + 2.279e-5 * tfilm - 1.343e-9 * tfilm.powi(2);
+            // Equation 2c. Because dynamic_viscosity is in lb/ft-hr, we must convert wind speed to ft/hr.
+            let nre = diameter * pf * (wind_speed * 60.0 * 60.0) / uf;
+
+            (pf, kf, nre, 1.825)
+        }
+        Units::SI => {
+            // Dynamic viscosity μf, kg/(m*s)
+            let uf = 1.458e-6 * (tfilm + 273.0).powf(1.5) / (tfilm + 383.4);
+
+            // Air density ρf, kg/m^3
+            let pf = (1.293 - 1.525e-4 * elevation + 6.379e-9 * elevation.powi(2)) / (1.0 + 0.00367 * tfilm);
+
+            // Thermal conductivity of air kf, W/(m*°C)
+            let kf = 2.424e-2 + 7.477e-5 * tfilm - 4.407e-9 * tfilm.powi(2);
+
+            // Reynolds number, no ft/hr conversion needed (wind speed already in m/s).
+            let nre = diameter * pf * wind_speed / uf;
+
+            (pf, kf, nre, 3.645)
+        }
+    };
 
     // Section 4.4.3.2, eq 5a 5b, page 12
     // qc0 = natural_convection
-    let qc0 = 1.825
+    let qc0 = qcn_coefficient
         * pf.powf(0.5)
         * diameter.powf(0.75)
         * (conductor_temperature - ambient_temperature).powf(1.25);
@@ -72,23 +136,38 @@ pub fn convective_heat_loss(
     // IEEE 738 recommends taking max of 3a / 3b results.
     // The convective heat loss is the bigger of forced and natural convection
     // From section 4.4.3 in the standard, page 10.
-    f64::max(qc0, f64::max(qc1, qc2))
+    let (loss, regime) = if qc0 >= qc1 && qc0 >= qc2 {
+        (qc0, ConvectionRegime::Natural)
+    } else if qc1 >= qc2 {
+        (qc1, ConvectionRegime::ForcedLowWind)
+    } else {
+        (qc2, ConvectionRegime::ForcedHighWind)
+    };
+
+    ConvectiveHeatLoss { loss, regime }
 }
 
-/// Returns radiated_heat_loss Qr (Watts / ft)
+/// Returns radiated_heat_loss Qr (Watts / ft, or Watts / m for `Units::SI`)
 /// # Arguments
+/// * `units` - Imperial or SI input/output convention
 /// * `ambient_temperature` - T_a: Degrees (C)
 /// * `conductor_temperature` - T_s: Conductor Surface Temperature (C)
 /// * `emissivity` - ε: Epsilon, Emissivity of conductor (0.0 to 1.0)
-/// * `diameter` - D_0: Outer diameter of the conductor (ft)
+/// * `diameter` - D_0: Outer diameter of the conductor (ft, or m for `Units::SI`)
 pub fn radiated_heat_loss(
+    units: Units,
     ambient_temperature: f64,
     conductor_temperature: f64,
     emissivity: f64,
     diameter: f64,
 ) -> f64 {
     // Section 4.4.4, eq 7a 7b, page 12
-    1.656
+    let coefficient = match units {
+        Units::Imperial => 1.656,
+        Units::SI => 17.8,
+    };
+
+    coefficient
         * diameter
         * emissivity
         * (
@@ -97,13 +176,13 @@ pub fn radiated_heat_loss(
         )
 }
 
-/// Calculates day of year. 
+/// Calculates day of year.
 /// # Arguments
 /// * `month` - Month January (1) to December (12)
 /// * `day_of_month` - Day of Month, 1 to 31
 pub fn day_of_year(month: i32, day_of_month: i32) -> i32 {
     let days_in_month = [0, 31, 28, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31];
-    
+
     let mut result = day_of_month;
     for i in 1..month {
         result += days_in_month[i as usize];
@@ -111,88 +190,143 @@ pub fn day_of_year(month: i32, day_of_month: i32) -> i32 {
     result
 }
 
-/// Returns solar_heat_gain Qs (Watts / ft)
+/// Returns solar_heat_gain Qs (Watts / ft, or Watts / m for `Units::SI`)
 /// # Arguments
-/// * `solar_radiation` - w/ft^2, or <0 if it should be calculated via month/day/hour
+/// * `units` - Imperial (ft) or SI (m) input/output convention
+/// * `solar_radiation` - w/ft^2 (or w/m^2 for `Units::SI`), or <0 if it should be calculated via month/day/hour
 /// * `month` - 1 (January) to 12 (December)
 /// * `day_of_month` - Day of Month (1-31)
 /// * `hour_of_day` - Hour of Day, 0 to 23 (e.g. 11:00 AM => 11)
 /// * `latitude_deg` - Lat: Latitude (Decimal Degrees)
 /// * `line_azimuth_deg` - Z_l: If line runs E-W => 90 Degrees
-/// * `elevation` - H_e: Height of conductor above sea level (ft)
+/// * `elevation` - H_e: Height of conductor above sea level (ft, or m for `Units::SI`)
 /// * `atmosphere_clear` - Clear? (True) Industrial? (False)
 /// * `absorptivity` - α: Alpha, Absorptivity of conductor (0.0 to 1.0)
-/// * `diameter` - D_0: Outer diameter of the conductor (ft)
+/// * `diameter` - D_0: Outer diameter of the conductor (ft, or m for `Units::SI`)
 pub fn solar_heat_gain(
+    units: Units,
     solar_radiation: f64,
     month: i32,
     day_of_month: i32,
     hour_of_day: f64,
-    latitude_deg: f64, 
+    latitude_deg: f64,
     line_azimuth_deg: f64,
-    elevation: f64, 
+    elevation: f64,
     atmosphere_clear: bool,
     absorptivity: f64,
-    diameter: f64, 
+    diameter: f64,
 ) -> f64 {
     // If solar radiation is already specified, immediately return the value.
     if solar_radiation >= 0.0 {
         return absorptivity * solar_radiation * diameter;
     }
 
-    // Constants
     let pi = std::f64::consts::PI;
 
-    let day_of_year = day_of_year(month, day_of_month);
-
-    let latitude_rad = latitude_deg * (pi / 180.0);
-
-    // Hour angle relative to noon, 15*(Time-12), at 11AM, Time = 11 and the Hour angle= –15 deg 
-    let w_deg = (hour_of_day - 12.0) * 15.0;
-    let w_rad = w_deg * (pi / 180.0);
-
     // Table 3 - Atmosphere condition coefficients
     let (a, b, c, d, e, f, g) = match atmosphere_clear {
         true => (-3.9241, 5.9276, -1.7856e-1, 3.223e-3, -3.3549e-5, 1.8053e-7, -3.7868e-10),
         false => (4.9408, 1.3208, 6.1444e-2, -2.9411e-3, 5.07752e-5, -4.03627e-7, 1.22967e-9),
     };
 
-    // Table H.5 - Solar heat multiplying factors, Ksolar for high altitudes
-    let mult = match elevation {
-        _ if elevation > 15000.0 => 1.3,
-        _ if elevation > 10000.0 => 1.25,
-        _ if elevation > 5000.0 => 1.15,
-        _ => 1.0,
+    let pos = solar_position(units, month, day_of_month, hour_of_day, latitude_deg, line_azimuth_deg, elevation);
+
+    // Convert to degrees.
+    let hc_deg = pos.hc_rad * (180.0 / pi);
+
+    // Equation 18 - Total solar and sky radiated heat intensity
+    let qs = a + b * hc_deg + c * hc_deg.powi(2) + d * hc_deg.powi(3) + e * hc_deg.powi(4) + f * hc_deg.powi(5) + g * hc_deg.powi(6);
+
+    // The Table 3 coefficients above give W/ft^2; SI expects W/m^2 (10.7639 ft^2 per m^2).
+    let qs = match units {
+        Units::Imperial => qs,
+        Units::SI => qs * 10.7639,
     };
 
+    // Equation 8 - Total solar and sky radiated heat intensity corrected for elevation
+    // Qs sometimes can compute as less than 0, if the sun is down. The lowest heating you can have is 0.
+    let qse = f64::max(qs,0.0) * pos.mult * pos.ksolar;
+
+    // Compute solar_heat_flux
+    absorptivity * qse * pos.theta.sin() * diameter
+}
+
+/// Solar position and altitude-correction terms shared by `solar_heat_gain`
+/// and `solar_heat_gain_turbidity`: the two differ only in how they turn
+/// solar altitude into a heat intensity `Qs` (hardcoded clear/industrial
+/// polynomials vs. transmissivity/cloud-cover physics), not in the
+/// underlying sun-position geometry.
+struct SolarPosition {
+    /// Hc: Solar altitude (Radians)
+    hc_rad: f64,
+    /// Table H.5 solar heat multiplying factor for high altitudes.
+    mult: f64,
+    /// Equation 20 solar altitude correction factor.
+    ksolar: f64,
+    /// Theta: Effective angle of incidence of the sun's rays (Radians)
+    theta: f64,
+}
+
+fn solar_position(
+    units: Units,
+    month: i32,
+    day_of_month: i32,
+    hour_of_day: f64,
+    latitude_deg: f64,
+    line_azimuth_deg: f64,
+    elevation: f64,
+) -> SolarPosition {
+    let pi = std::f64::consts::PI;
+
+    let day_of_year = day_of_year(month, day_of_month);
+
+    let latitude_rad = latitude_deg * (pi / 180.0);
+
+    // Hour angle relative to noon, 15*(Time-12), at 11AM, Time = 11 and the Hour angle= –15 deg
+    let w_deg = (hour_of_day - 12.0) * 15.0;
+    let w_rad = w_deg * (pi / 180.0);
+
     // Equation 16b - 23.4583 more precisely from Annex A
     let p_rad = (((284.0 + (day_of_year as f64)) / 365.0) * 360.0) * (pi / 180.0);
     let delta_rad = (23.4583 * p_rad.sin()) * (pi / 180.0);
 
-    // Equation 16a
+    // Equation 16a - Solar altitude Hc
     let hc_rad = (latitude_rad.cos() * delta_rad.cos() * w_rad.cos() + latitude_rad.sin() * delta_rad.sin()).asin();
-    // Limit to 0-90 range. Convert to degrees.
-    let hc_deg = hc_rad * (180.0 / pi);
 
-    // Equation 18 - Total solar and sky radiated heat intensity
-    let qs = a + b * hc_deg + c * hc_deg.powi(2) + d * hc_deg.powi(3) + e * hc_deg.powi(4) + f * hc_deg.powi(5) + g * hc_deg.powi(6);
-
-    // Equation 20 - Solar altitude correction factor
-    let ksolar = 1.0 + 3.5e-5 * elevation - 1.0e-9 * elevation.powi(2);
+    // Table H.5 - Solar heat multiplying factors, Ksolar for high altitudes.
+    // The SI thresholds are the same altitudes converted from feet to meters.
+    let mult = match units {
+        Units::Imperial => match elevation {
+            _ if elevation > 15000.0 => 1.3,
+            _ if elevation > 10000.0 => 1.25,
+            _ if elevation > 5000.0 => 1.15,
+            _ => 1.0,
+        },
+        Units::SI => match elevation {
+            _ if elevation > 4572.0 => 1.3,
+            _ if elevation > 3048.0 => 1.25,
+            _ if elevation > 1524.0 => 1.15,
+            _ => 1.0,
+        },
+    };
 
-    // Equation 8 - Total solar and sky radiated heat intensity corrected for elevation
-    // Qs sometimes can compute as less than 0, if the sun is down. The lowest heating you can have is 0.
-    let qse = f64::max(qs,0.0) * mult * ksolar;
+    // Equation 20 - Solar altitude correction factor.
+    // The SI coefficients are calibrated for elevation in meters (see
+    // `ieee738::solar_heat_gain_position`); the Imperial ones are for feet.
+    let ksolar = match units {
+        Units::Imperial => 1.0 + 3.5e-5 * elevation - 1.0e-9 * elevation.powi(2),
+        Units::SI => 1.0 + 1.148e-4 * elevation - 1.108e-8 * elevation.powi(2),
+    };
 
     // Equation 17b
     let x = w_rad.sin() / ((latitude_rad.sin() * w_rad.cos() - latitude_rad.cos() * delta_rad.tan()));
 
-    let cc_deg = 
+    let cc_deg =
         if -180.0 <= w_deg && w_deg < 0.0 {
-            if x >= 0.0 { 0.0 } 
+            if x >= 0.0 { 0.0 }
             else { 180.0 }
         } else {
-            if x < 0.0 { 180.0 } 
+            if x < 0.0 { 180.0 }
             else { 360.0 }
         };
 
@@ -207,8 +341,116 @@ pub fn solar_heat_gain(
     // Equation 9 - Effective angle of incidence of the sun’s rays
     let theta = (hc_rad.cos() * (zc_rad - zl_rad).cos()).acos();
 
+    SolarPosition { hc_rad, mult, ksolar, theta }
+}
+
+pub fn solar_heat_gain(
+    units: Units,
+    solar_radiation: f64,
+    month: i32,
+    day_of_month: i32,
+    hour_of_day: f64,
+    latitude_deg: f64,
+    line_azimuth_deg: f64,
+    elevation: f64,
+    atmosphere_clear: bool,
+    absorptivity: f64,
+    diameter: f64,
+) -> f64 {
+    // If solar radiation is already specified, immediately return the value.
+    if solar_radiation >= 0.0 {
+        return absorptivity * solar_radiation * diameter;
+    }
+
+    let pi = std::f64::consts::PI;
+
+    // Table 3 - Atmosphere condition coefficients
+    let (a, b, c, d, e, f, g) = match atmosphere_clear {
+        true => (-3.9241, 5.9276, -1.7856e-1, 3.223e-3, -3.3549e-5, 1.8053e-7, -3.7868e-10),
+        false => (4.9408, 1.3208, 6.1444e-2, -2.9411e-3, 5.07752e-5, -4.03627e-7, 1.22967e-9),
+    };
+
+    let pos = solar_position(units, month, day_of_month, hour_of_day, latitude_deg, line_azimuth_deg, elevation);
+
+    // Convert to degrees.
+    let hc_deg = pos.hc_rad * (180.0 / pi);
+
+    // Equation 18 - Total solar and sky radiated heat intensity
+    let qs = a + b * hc_deg + c * hc_deg.powi(2) + d * hc_deg.powi(3) + e * hc_deg.powi(4) + f * hc_deg.powi(5) + g * hc_deg.powi(6);
+
+    // The Table 3 coefficients above give W/ft^2; SI expects W/m^2 (10.7639 ft^2 per m^2).
+    let qs = match units {
+        Units::Imperial => qs,
+        Units::SI => qs * 10.7639,
+    };
+
+    // Equation 8 - Total solar and sky radiated heat intensity corrected for elevation
+    // Qs sometimes can compute as less than 0, if the sun is down. The lowest heating you can have is 0.
+    let qse = f64::max(qs,0.0) * pos.mult * pos.ksolar;
+
     // Compute solar_heat_flux
-    absorptivity * qse * (theta).sin() * diameter
+    absorptivity * qse * pos.theta.sin() * diameter
+}
+
+/// Returns solar_heat_gain Qs (Watts / ft, or Watts / m for `Units::SI`),
+/// computing the direct-beam flux physically from atmospheric
+/// transmissivity and cloud cover instead of picking between the two
+/// hardcoded clear/industrial polynomial coefficient sets used by
+/// `solar_heat_gain`. Real sites sit on a continuum of haze and cloud
+/// cover rather than only those two cases.
+/// # Arguments
+/// * `units` - Imperial (ft) or SI (m) input/output convention
+/// * `month` - 1 (January) to 12 (December)
+/// * `day_of_month` - Day of Month (1-31)
+/// * `hour_of_day` - Hour of Day, 0 to 23 (e.g. 11:00 AM => 11)
+/// * `latitude_deg` - Lat: Latitude (Decimal Degrees)
+/// * `line_azimuth_deg` - Z_l: If line runs E-W => 90 Degrees
+/// * `elevation` - H_e: Height of conductor above sea level (ft, or m for `Units::SI`)
+/// * `transmissivity` - τ: Single-way atmospheric transmissivity (0.0 to 1.0)
+/// * `cloud_cover` - CC: Cloud cover fraction (0.0 to 1.0)
+/// * `absorptivity` - α: Alpha, Absorptivity of conductor (0.0 to 1.0)
+/// * `diameter` - D_0: Outer diameter of the conductor (ft, or m for `Units::SI`)
+pub fn solar_heat_gain_turbidity(
+    units: Units,
+    month: i32,
+    day_of_month: i32,
+    hour_of_day: f64,
+    latitude_deg: f64,
+    line_azimuth_deg: f64,
+    elevation: f64,
+    transmissivity: f64,
+    cloud_cover: f64,
+    absorptivity: f64,
+    diameter: f64,
+) -> f64 {
+    let pi = std::f64::consts::PI;
+
+    let day_of_year = day_of_year(month, day_of_month);
+
+    let pos = solar_position(units, month, day_of_month, hour_of_day, latitude_deg, line_azimuth_deg, elevation);
+
+    // Solar constant, 1367 W/m^2 converted to W/ft^2 for the Imperial unit system.
+    let solar_constant = match units {
+        Units::Imperial => 1367.0 / 10.7639,
+        Units::SI => 1367.0,
+    };
+
+    // Eccentricity correction - extraterrestrial normal irradiance I0
+    let i0 = solar_constant * (1.0 + 0.033 * (2.0 * pi * (day_of_year as f64) / 365.0).cos());
+
+    // Air-mass factor; Hc must be above the horizon for the direct-beam term to be defined.
+    let air_mass = 1.0 / pos.hc_rad.sin().max(1.0e-6);
+
+    // Direct-beam flux attenuated by atmospheric transmissivity
+    let qdir = i0 * transmissivity.powf(air_mass);
+
+    // Reduce by cloud cover
+    let qtotal = qdir * (1.0 - 0.75 * cloud_cover.powf(3.4));
+
+    // Qs sometimes can compute as less than 0, if the sun is down. The lowest heating you can have is 0.
+    let qse = f64::max(qtotal, 0.0) * pos.mult * pos.ksolar;
+
+    absorptivity * qse * pos.theta.sin() * diameter
 }
 
 /// Returns resistance, adjusted to given conductor_temperature.
@@ -219,11 +461,11 @@ pub fn solar_heat_gain(
 /// * `r_low` - Resistance at Low Temperature, Ohms
 /// * `r_high` - Resistance at High Temperature, Ohms
 pub fn adjust_r(
-    conductor_temperature: f64, 
-    t_low: f64, 
-    t_high: f64, 
-    r_low: f64, 
-    r_high: f64, 
+    conductor_temperature: f64,
+    t_low: f64,
+    t_high: f64,
+    r_low: f64,
+    r_high: f64,
 ) -> f64 {
     // Equation 10
     let ohms_per_c: f64 = (r_high - r_low) / (t_high - t_low);
@@ -232,26 +474,28 @@ pub fn adjust_r(
 
 /// Returns thermal_rating (Amps)
 /// # Arguments
-/// * `solar_radiation` - w/ft^2, or <0 if it should be calculated via month/day/hour
+/// * `units` - Imperial or SI input/output convention
+/// * `solar_radiation` - w/ft^2 (or w/m^2 for `Units::SI`), or <0 if it should be calculated via month/day/hour
 /// * `month` - 1 (January) to 12 (December)
 /// * `day_of_month` - Day of Month (1-31)
 /// * `hour_of_day` - Hour of Day, 0 to 23 (e.g. 11:00 AM => 11)
 /// * `ambient_temperature` - T_a: Degrees (C)
-/// * `wind_speed` - V_w: Wind Speed (ft/s)
+/// * `wind_speed` - V_w: Wind Speed (ft/s, or m/s for `Units::SI`)
 /// * `wind_angle_deg` - Wind Angle (Degrees) 0 to 90
 /// * `latitude_deg` - Lat: Latitude (Decimal Degrees)
 /// * `line_azimuth_deg` - Z_l: If line runs E-W => 90 Degrees
-/// * `elevation` - H_e: Height of conductor above sea level (ft)
+/// * `elevation` - H_e: Height of conductor above sea level (ft, or m for `Units::SI`)
 /// * `atmosphere_clear` - Clear? (True) Industrial? (False)
 /// * `conductor_temperature` - T_s: Conductor Surface Temperature (C)
 /// * `absorptivity` - α: Alpha, Absorptivity of conductor (0.0 to 1.0)
 /// * `emissivity` - ε: Epsilon, Emissivity of conductor (0.0 to 1.0)
-/// * `diameter` - D_0: Outer diameter of the conductor (ft)
+/// * `diameter` - D_0: Outer diameter of the conductor (ft, or m for `Units::SI`)
 /// * `t_low` - Low Temperature, Degrees C
 /// * `t_high` - High Temperature, Degrees C
 /// * `r_low` - Resistance at Low Temperature, Ohms
 /// * `r_high` - Resistance at High Temperature, Ohms
 pub fn thermal_rating(
+    units: Units,
     solar_radiation: f64,
     month: i32,
     day_of_month: i32,
@@ -272,52 +516,123 @@ pub fn thermal_rating(
     r_low: f64,
     r_high: f64,
 ) -> f64 {
+    thermal_rating_detailed(
+        units,
+        solar_radiation,
+        month,
+        day_of_month,
+        hour_of_day,
+        ambient_temperature,
+        wind_speed,
+        wind_angle_deg,
+        latitude_deg,
+        line_azimuth_deg,
+        elevation,
+        atmosphere_clear,
+        conductor_temperature,
+        absorptivity,
+        emissivity,
+        diameter,
+        t_low,
+        t_high,
+        r_low,
+        r_high,
+    ).current
+}
 
-    if conductor_temperature < ambient_temperature {
-        return 0.0;
-    }
+/// Full heat-balance breakdown behind a `thermal_rating` result: Qc (plus
+/// which of natural/forced convection won), Qr, Qs, the temperature-adjusted
+/// resistance R, and the resulting current, so callers can log and
+/// visualize the full balance from one call instead of recomputing each
+/// term themselves.
+pub struct HeatBalance {
+    pub convective_loss: f64,
+    pub convection_regime: ConvectionRegime,
+    pub radiated_loss: f64,
+    pub solar_gain: f64,
+    pub resistance: f64,
+    pub current: f64,
+}
 
-    let qc = convective_heat_loss(ambient_temperature,wind_speed,wind_angle_deg,elevation,conductor_temperature,diameter);
+/// Same calculation as `thermal_rating`, but returns the full `HeatBalance`
+/// breakdown instead of a bare ampacity scalar.
+pub fn thermal_rating_detailed(
+    units: Units,
+    solar_radiation: f64,
+    month: i32,
+    day_of_month: i32,
+    hour_of_day: f64,
+    ambient_temperature: f64,
+    wind_speed: f64,
+    wind_angle_deg: f64,
+    latitude_deg: f64,
+    line_azimuth_deg: f64,
+    elevation: f64,
+    atmosphere_clear: bool,
+    conductor_temperature: f64,
+    absorptivity: f64,
+    emissivity: f64,
+    diameter: f64,
+    t_low: f64,
+    t_high: f64,
+    r_low: f64,
+    r_high: f64,
+) -> HeatBalance {
+    let convective = convective_heat_loss_detailed(units,ambient_temperature,wind_speed,wind_angle_deg,elevation,conductor_temperature,diameter);
 
-    let qr = radiated_heat_loss(ambient_temperature,conductor_temperature,emissivity,diameter);
+    let qr = radiated_heat_loss(units,ambient_temperature,conductor_temperature,emissivity,diameter);
 
-    let qs: f64 = solar_heat_gain(solar_radiation,month,day_of_month,hour_of_day,latitude_deg,line_azimuth_deg,elevation,atmosphere_clear,absorptivity,diameter);
+    let qs: f64 = solar_heat_gain(units,solar_radiation,month,day_of_month,hour_of_day,latitude_deg,line_azimuth_deg,elevation,atmosphere_clear,absorptivity,diameter);
 
     let r = adjust_r(conductor_temperature,t_low,t_high,r_low,r_high);
 
-    if qc + qr - qs < 0 {
-        // The ambient temperature + solar heating, has brought the conductor to a higher temperature than the specified MOT "conductor_temperature"
-        return 0.0;
-    }
+    let current =
+        if conductor_temperature < ambient_temperature {
+            0.0
+        } else if convective.loss + qr - qs < 0.0 {
+            // The ambient temperature + solar heating, has brought the conductor to a higher temperature than the specified MOT "conductor_temperature"
+            0.0
+        } else {
+            ((convective.loss + qr - qs) / r).powf(0.5)
+        };
 
-    ((qc + qr - qs) / r).powf(0.5)
+    HeatBalance {
+        convective_loss: convective.loss,
+        convection_regime: convective.regime,
+        radiated_loss: qr,
+        solar_gain: qs,
+        resistance: r,
+        current,
+    }
 }
 
 
 
 /// Returns calculated_temperature (C) based on input conditions
 /// # Arguments
-/// * `solar_radiation` - w/ft^2, or <0 if it should be calculated via month/day/hour
+/// * `units` - Imperial or SI input/output convention
+/// * `solar_radiation` - w/ft^2 (or w/m^2 for `Units::SI`), or <0 if it should be calculated via month/day/hour
 /// * `month` - 1 (January) to 12 (December)
 /// * `day_of_month` - Day of Month (1-31)
 /// * `hour_of_day` - Hour of Day, 0 to 23 (e.g. 11:00 AM => 11)
 /// * `ambient_temperature` - T_a: Degrees (C)
-/// * `wind_speed` - V_w: Wind Speed (ft/s)
+/// * `wind_speed` - V_w: Wind Speed (ft/s, or m/s for `Units::SI`)
 /// * `wind_angle_deg` - Wind Angle (Degrees) 0 to 90
 /// * `latitude_deg` - Lat: Latitude (Decimal Degrees)
 /// * `line_azimuth_deg` - Z_l: If line runs E-W => 90 Degrees
-/// * `elevation` - H_e: Height of conductor above sea level (ft)
+/// * `elevation` - H_e: Height of conductor above sea level (ft, or m for `Units::SI`)
 /// * `atmosphere_clear` - Clear? (True) Industrial? (False)
 /// * `current` - Current (amps)
 /// * `tolerance` - Tolerance on result (amps)
 /// * `absorptivity` - α: Alpha, Absorptivity of conductor (0.0 to 1.0)
 /// * `emissivity` - ε: Epsilon, Emissivity of conductor (0.0 to 1.0)
-/// * `diameter` - D_0: Outer diameter of the conductor (ft)
+/// * `diameter` - D_0: Outer diameter of the conductor (ft, or m for `Units::SI`)
 /// * `t_low` - Low Temperature, Degrees C
 /// * `t_high` - High Temperature, Degrees C
 /// * `r_low` - Resistance at Low Temperature, Ohms
 /// * `r_high` - Resistance at High Temperature, Ohms
 pub fn calculated_temperature(
+    units: Units,
     solar_radiation: f64,
     month: i32,
     day_of_month: i32,
@@ -349,7 +664,8 @@ pub fn calculated_temperature(
 
     // Increase upper_bound until y(upper_bound) exceeds target_y or it becomes very large
     while thermal_rating(
-        solar_radiation
+        units
+        ,solar_radiation
         ,month
         ,day_of_month
         ,hour_of_day
@@ -376,7 +692,8 @@ pub fn calculated_temperature(
     while upper_bound - lower_bound > tolerance {
         let mid = (lower_bound + upper_bound) / 2.0;
         let mid_y = thermal_rating(
-            solar_radiation
+            units
+            ,solar_radiation
             ,month
             ,day_of_month
             ,hour_of_day
@@ -411,16 +728,17 @@ pub fn calculated_temperature(
 
 /// Returns conductor_temperature_rise (C)
 /// # Arguments
-/// * `solar_radiation` - w/ft^2, or <0 if it should be calculated via month/day/hour
+/// * `units` - Imperial or SI input/output convention
+/// * `solar_radiation` - w/ft^2 (or w/m^2 for `Units::SI`), or <0 if it should be calculated via month/day/hour
 /// * `month` - 1 (January) to 12 (December)
 /// * `day_of_month` - Day of Month (1-31)
 /// * `hour_of_day` - Hour of Day, 0 to 23 (e.g. 11:00 AM => 11)
 /// * `ambient_temperature` - T_a: Degrees (C)
-/// * `wind_speed` - V_w: Wind Speed (ft/s)
+/// * `wind_speed` - V_w: Wind Speed (ft/s, or m/s for `Units::SI`)
 /// * `wind_angle_deg` - Wind Angle (Degrees) 0 to 90
 /// * `latitude_deg` - Lat: Latitude (Decimal Degrees)
 /// * `line_azimuth_deg` - Z_l: If line runs E-W => 90 Degrees
-/// * `elevation` - H_e: Height of conductor above sea level (ft)
+/// * `elevation` - H_e: Height of conductor above sea level (ft, or m for `Units::SI`)
 /// * `atmosphere_clear` - Clear? (True) Industrial? (False)
 /// * `conductor_temperature` - Initial Conductor Surface Temperature (C)
 /// * `current` - Current (amps)
@@ -428,13 +746,19 @@ pub fn calculated_temperature(
 /// * `steps` - Number of time steps to apply
 /// * `absorptivity` - α: Alpha, Absorptivity of conductor (0.0 to 1.0)
 /// * `emissivity` - ε: Epsilon, Emissivity of conductor (0.0 to 1.0)
-/// * `diameter` - D_0: Outer diameter of the conductor (ft)
+/// * `diameter` - D_0: Outer diameter of the conductor (ft, or m for `Units::SI`)
 /// * `t_low` - Low Temperature, Degrees C
 /// * `t_high` - High Temperature, Degrees C
 /// * `r_low` - Resistance at Low Temperature, Ohms
 /// * `r_high` - Resistance at High Temperature, Ohms
-/// * `heat_capacity` - m*Cp: Total heat capacity of conductor (J/(ft-°C))
+/// * `heat_capacity` - m*Cp: Total heat capacity of conductor (J/(ft-°C), or J/(m-°C) for `Units::SI`)
+/// * `adaptive_tolerance` - If `Some(tolerance)`, each step is taken once at
+///   full size and twice at half size via RK4; the step is halved (and
+///   retried) until the two agree within `tolerance` or the step falls
+///   below 1e-6 seconds, where it is accepted as is. If `None`, a single
+///   RK4 step of `time_step` is taken per step with no adaptation.
 pub fn conductor_temperature_rise(
+    units: Units,
     solar_radiation: f64,
     month: i32,
     day_of_month: i32,
@@ -458,38 +782,158 @@ pub fn conductor_temperature_rise(
     r_low: f64,
     r_high: f64,
     heat_capacity: f64,
+    adaptive_tolerance: Option<f64>,
 ) -> f64 {
 
     if conductor_temperature < ambient_temperature {
         return 0.0;
     }
 
+    // Qs is independent of conductor temperature and current, so it stays
+    // fixed for the whole integration and only needs to be computed once.
+    let qs: f64 = solar_heat_gain(units,solar_radiation,month,day_of_month,hour_of_day,latitude_deg,line_azimuth_deg,elevation,atmosphere_clear,absorptivity,diameter);
+
+    // dT/dt = (I^2*R(T) + Qs - Qc(T) - Qr(T)) / heat_capacity
+    let f = |t: f64| -> f64 {
+        let qc = convective_heat_loss(units,ambient_temperature,wind_speed,wind_angle_deg,elevation,t,diameter);
+        let qr = radiated_heat_loss(units,ambient_temperature,t,emissivity,diameter);
+        let r = adjust_r(t,t_low,t_high,r_low,r_high);
+        ((r * current.powf(2.0)) + qs - qc - qr) / heat_capacity
+    };
+
+    // Classic 4th-order Runge-Kutta update of `t` over a step of size `dt`.
+    let rk4_step = |t: f64, dt: f64| -> f64 {
+        let k1 = f(t);
+        let k2 = f(t + 0.5 * dt * k1);
+        let k3 = f(t + 0.5 * dt * k2);
+        let k4 = f(t + dt * k3);
+        t + dt * (k1 + 2.0 * k2 + 2.0 * k3 + k4) / 6.0
+    };
+
+    // Covers the full `dt` via a sequence of sub-steps, each taken as one
+    // full step and two half steps via RK4; a sub-step is accepted once the
+    // two agree within `tolerance` (or once it has shrunk below 1e-6 seconds),
+    // otherwise it is halved and retried. This
+    // is the standard embedded-RK step-doubling/halving loop: it always
+    // advances elapsed time by exactly `dt` in total, it just may take more
+    // than one accepted sub-step to get there.
+    let adaptive_rk4_step = |t: f64, dt: f64, tolerance: f64| -> f64 {
+        let mut elapsed = 0.0;
+        let mut current_t = t;
+        let mut h = dt;
+
+        while elapsed < dt {
+            // Don't overshoot the requested total step.
+            h = h.min(dt - elapsed);
+
+            let full_step = rk4_step(current_t, h);
+            let half_step = rk4_step(rk4_step(current_t, h / 2.0), h / 2.0);
+
+            if (full_step - half_step).abs() <= tolerance || h < 1.0e-6 {
+                current_t = half_step;
+                elapsed += h;
+            } else {
+                h /= 2.0;
+            }
+        }
+
+        current_t
+    };
+
     let mut final_temperature = conductor_temperature;
 
     for _ in 0..steps {
-        let qc = convective_heat_loss(ambient_temperature,wind_speed,wind_angle_deg,elevation,final_temperature,diameter);
-        let qr = radiated_heat_loss(ambient_temperature,final_temperature,emissivity,diameter);
-        let qs: f64 = solar_heat_gain(solar_radiation,month,day_of_month,hour_of_day,latitude_deg,line_azimuth_deg,elevation,atmosphere_clear,absorptivity,diameter);
+        final_temperature = match adaptive_tolerance {
+            Some(tolerance) => adaptive_rk4_step(final_temperature, time_step, tolerance),
+            None => rk4_step(final_temperature, time_step),
+        };
+    }
+
+    final_temperature - conductor_temperature
+}
+
+/// Plays back a weather time series and returns the full conductor
+/// temperature trajectory, one forward-Euler step per entry, so a measured
+/// or forecast weather day can be checked for whether the conductor ever
+/// exceeds its MOT. Unlike `conductor_temperature_rise`, ambient
+/// temperature, wind, and solar input vary per step instead of being held
+/// fixed across `steps`.
+/// # Arguments
+/// * `units` - Imperial or SI input/output convention
+/// * `ambient_temperature` - T_a per time step: Degrees (C)
+/// * `wind_speed` - V_w per time step: Wind Speed (ft/s, or m/s for `Units::SI`)
+/// * `wind_angle_deg` - Wind Angle per time step (Degrees) 0 to 90
+/// * `solar_radiation` - w/ft^2 per time step (or w/m^2 for `Units::SI`); must be the direct measured flux, not the <0 sentinel used by `solar_heat_gain` to derive it from date/time
+/// * `elevation` - H_e: Height of conductor above sea level (ft, or m for `Units::SI`)
+/// * `conductor_temperature` - Initial Conductor Surface Temperature (C)
+/// * `current` - Current (amps)
+/// * `time_step` - Timestep (seconds)
+/// * `absorptivity` - α: Alpha, Absorptivity of conductor (0.0 to 1.0)
+/// * `emissivity` - ε: Epsilon, Emissivity of conductor (0.0 to 1.0)
+/// * `diameter` - D_0: Outer diameter of the conductor (ft, or m for `Units::SI`)
+/// * `t_low` - Low Temperature, Degrees C
+/// * `t_high` - High Temperature, Degrees C
+/// * `r_low` - Resistance at Low Temperature, Ohms
+/// * `r_high` - Resistance at High Temperature, Ohms
+/// * `heat_capacity` - m*Cp: Total heat capacity of conductor (J/(ft-°C), or J/(m-°C) for `Units::SI`)
+pub fn conductor_temperature_trajectory(
+    units: Units,
+    ambient_temperature: &[f64],
+    wind_speed: &[f64],
+    wind_angle_deg: &[f64],
+    solar_radiation: &[f64],
+    elevation: f64,
+    conductor_temperature: f64,
+    current: f64,
+    time_step: f64,
+    absorptivity: f64,
+    emissivity: f64,
+    diameter: f64,
+    t_low: f64,
+    t_high: f64,
+    r_low: f64,
+    r_high: f64,
+    heat_capacity: f64,
+) -> Vec<f64> {
+    let steps = ambient_temperature.len()
+        .min(wind_speed.len())
+        .min(wind_angle_deg.len())
+        .min(solar_radiation.len());
+
+    let mut trajectory = Vec::with_capacity(steps + 1);
+    trajectory.push(conductor_temperature);
+
+    let mut final_temperature = conductor_temperature;
+
+    for i in 0..steps {
+        let qc = convective_heat_loss(units,ambient_temperature[i],wind_speed[i],wind_angle_deg[i],elevation,final_temperature,diameter);
+        let qr = radiated_heat_loss(units,ambient_temperature[i],final_temperature,emissivity,diameter);
+        // month/day_of_month/hour_of_day/latitude/line_azimuth/atmosphere_clear are
+        // unused here: solar_radiation[i] is always a direct measurement, so
+        // solar_heat_gain's date/time/geometry branch never runs.
+        let qs: f64 = solar_heat_gain(units,solar_radiation[i],1,1,0.0,0.0,0.0,elevation,true,absorptivity,diameter);
         let r = adjust_r(final_temperature,t_low,t_high,r_low,r_high);
         let delta_t = ((r * current.powf(2.0)) + qs - qc - qr) * time_step / heat_capacity;
         final_temperature += delta_t;
+        trajectory.push(final_temperature);
     }
 
-    final_temperature - conductor_temperature
+    trajectory
 }
 
 /// Returns transient_rating (Amps)
 /// # Arguments
-/// * `solar_radiation` - w/ft^2, or <0 if it should be calculated via month/day/hour
+/// * `units` - Imperial or SI input/output convention
+/// * `solar_radiation` - w/ft^2 (or w/m^2 for `Units::SI`), or <0 if it should be calculated via month/day/hour
 /// * `month` - 1 (January) to 12 (December)
 /// * `day_of_month` - Day of Month (1-31)
 /// * `hour_of_day` - Hour of Day, 0 to 23 (e.g. 11:00 AM => 11)
 /// * `ambient_temperature` - T_a: Degrees (C)
-/// * `wind_speed` - V_w: Wind Speed (ft/s)
+/// * `wind_speed` - V_w: Wind Speed (ft/s, or m/s for `Units::SI`)
 /// * `wind_angle_deg` - Wind Angle (Degrees) 0 to 90
 /// * `latitude_deg` - Lat: Latitude (Decimal Degrees)
 /// * `line_azimuth_deg` - Z_l: If line runs E-W => 90 Degrees
-/// * `elevation` - H_e: Height of conductor above sea level (ft)
+/// * `elevation` - H_e: Height of conductor above sea level (ft, or m for `Units::SI`)
 /// * `atmosphere_clear` - Clear? (True) Industrial? (False)
 /// * `conductor_temperature` - Initial Conductor Surface Temperature (C)
 /// * `conductor_temperature_max` - Max Final Conductor Surface Temperature (C)
@@ -498,13 +942,15 @@ pub fn conductor_temperature_rise(
 /// * `tolerance` - Tolerance on result (amps)
 /// * `absorptivity` - α: Alpha, Absorptivity of conductor (0.0 to 1.0)
 /// * `emissivity` - ε: Epsilon, Emissivity of conductor (0.0 to 1.0)
-/// * `diameter` - D_0: Outer diameter of the conductor (ft)
+/// * `diameter` - D_0: Outer diameter of the conductor (ft, or m for `Units::SI`)
 /// * `t_low` - Low Temperature, Degrees C
 /// * `t_high` - High Temperature, Degrees C
 /// * `r_low` - Resistance at Low Temperature, Ohms
 /// * `r_high` - Resistance at High Temperature, Ohms
-/// * `heat_capacity` - m*Cp: Total heat capacity of conductor (J/(ft-°C))
+/// * `heat_capacity` - m*Cp: Total heat capacity of conductor (J/(ft-°C), or J/(m-°C) for `Units::SI`)
+/// * `adaptive_tolerance` - Forwarded to `conductor_temperature_rise`; `Some(tolerance)` enables adaptive RK4 step-halving, `None` for a fixed-size RK4 step.
 pub fn transient_rating(
+    units: Units,
     solar_radiation: f64,
     month: i32,
     day_of_month: i32,
@@ -529,6 +975,7 @@ pub fn transient_rating(
     r_low: f64,
     r_high: f64,
     heat_capacity: f64,
+    adaptive_tolerance: Option<f64>,
 ) -> f64 {
 
     if conductor_temperature_max < conductor_temperature {
@@ -539,11 +986,12 @@ pub fn transient_rating(
     let mut lower_bound: f64 = 0.0;
     let mut upper_bound: f64 = 4096.0;
     // delta_t_max
-    let target_y: f64 = conductor_temperature_max - conductor_temperature; 
+    let target_y: f64 = conductor_temperature_max - conductor_temperature;
 
     // Increase upper_bound until y(upper_bound) exceeds target_y or it becomes very large
     while conductor_temperature_rise(
-            solar_radiation
+            units
+            ,solar_radiation
             ,month
             ,day_of_month
             ,hour_of_day
@@ -566,6 +1014,7 @@ pub fn transient_rating(
             ,r_low
             ,r_high
             ,heat_capacity
+            ,adaptive_tolerance
         ) < target_y && upper_bound < f64::MAX / 2.0 {
         upper_bound *= 2.0;
     }
@@ -574,7 +1023,8 @@ pub fn transient_rating(
     while upper_bound - lower_bound > tolerance {
         let mid = (lower_bound + upper_bound) / 2.0;
         let mid_y = conductor_temperature_rise(
-            solar_radiation
+            units
+            ,solar_radiation
             ,month
             ,day_of_month
             ,hour_of_day
@@ -597,6 +1047,7 @@ pub fn transient_rating(
             ,r_low
             ,r_high
             ,heat_capacity
+            ,adaptive_tolerance
         );
 
         if mid_y < target_y {
@@ -609,4 +1060,3 @@ pub fn transient_rating(
     // Return the midpoint of the final range
     (lower_bound + upper_bound) / 2.0
 }
-
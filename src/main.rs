@@ -1,6 +1,8 @@
 // mod ieee738;
 mod ieee738_us;
 
+use ieee738_us::Units;
+
 fn main() {
     println!("Hello, world!");
     // Sample input values
@@ -57,7 +59,8 @@ fn main() {
     let r_high: f64 = 2.63258e-05;
 
     let rating = ieee738_us::thermal_rating(
-        solar_radiation
+        Units::Imperial
+        ,solar_radiation
         ,month
         ,day_of_month
         ,hour_of_day
@@ -82,7 +85,8 @@ fn main() {
 
     let tolerance: f64 = 0.01;
     let temperature = ieee738_us::calculated_temperature(
-        solar_radiation
+        Units::Imperial
+        ,solar_radiation
         ,month
         ,day_of_month
         ,hour_of_day
@@ -112,7 +116,8 @@ fn main() {
     let steps: i32 = 1;
 
     let delta_t = ieee738_us::conductor_temperature_rise(
-        solar_radiation
+        Units::Imperial
+        ,solar_radiation
         ,month
         ,day_of_month
         ,hour_of_day
@@ -135,6 +140,7 @@ fn main() {
         ,r_low
         ,r_high
         ,heat_capacity
+        ,None
     );
 
     println!("Delta T: {}", delta_t);
@@ -144,7 +150,8 @@ fn main() {
     let steps: i32 = 31;
 
     let t_rating: f64 = ieee738_us::transient_rating(
-        solar_radiation
+        Units::Imperial
+        ,solar_radiation
         ,month
         ,day_of_month
         ,hour_of_day
@@ -168,6 +175,7 @@ fn main() {
         ,r_low
         ,r_high
         ,heat_capacity
+        ,None
     );
 
     println!("Transient Rating: {}", t_rating);